@@ -0,0 +1,43 @@
+//! Additions to `Affine` that live outside its primary definition.
+//!
+//! `Affine` itself is defined elsewhere in the crate; this module only
+//! holds impls added after the fact, so they can land without touching
+//! that file.
+
+use std::ops::Mul;
+
+use crate::{Affine, Vec2};
+
+/// Transforms a vector, as opposed to [`Mul<Point>`](struct.Affine.html),
+/// which transforms a point.
+///
+/// Vectors represent a direction and magnitude (a tangent, a normal, a
+/// displacement) rather than a location, so translation does not apply to
+/// them; only the linear (2x2) part of the matrix — coefficients
+/// `[a, b, c, d]` — is used, with `e` and `f` ignored.
+impl Mul<Vec2> for Affine {
+    type Output = Vec2;
+
+    #[inline]
+    fn mul(self, other: Vec2) -> Vec2 {
+        let c = self.as_coeffs();
+        Vec2::new(
+            c[0] * other.x + c[2] * other.y,
+            c[1] * other.x + c[3] * other.y,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Affine, Vec2};
+
+    #[test]
+    fn affine_vector_ignores_translation() {
+        let a = Affine::new([2.0, 0.0, 0.0, 3.0, 5.0, 6.0]);
+        let v = Vec2::new(1.0, 1.0);
+
+        // The translation (5, 6) must not appear in the result.
+        assert_eq!(a * v, Vec2::new(2.0, 3.0));
+    }
+}
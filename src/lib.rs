@@ -0,0 +1,8 @@
+//! A 2D curves library.
+
+mod affine;
+mod transformable;
+mod translate_scale;
+
+pub use crate::transformable::Transformable;
+pub use crate::translate_scale::{ScaleTranslate, TranslateScale};
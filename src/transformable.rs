@@ -0,0 +1,147 @@
+//! A trait for applying transforms uniformly across shapes and paths.
+
+use crate::{
+    Affine, Arc, BezPath, CubicBez, Ellipse, Line, PathEl, PathSeg, Point, QuadBez, RoundedRect,
+    TranslateScale,
+};
+
+/// A type that can be transformed by an [`Affine`](struct.Affine.html) (or,
+/// more cheaply, a [`TranslateScale`](struct.TranslateScale.html)).
+///
+/// This unifies the scattered per-type `Mul` impls on `Affine`
+/// (`Affine * Point`, `Affine * Line`, ...) behind one interface, so generic code can map a
+/// whole path or shape through a transform without matching on element
+/// variants by hand. `transform_mut` additionally lets callers update
+/// geometry in place, which avoids reallocating a `BezPath`'s element
+/// buffer.
+///
+/// Not every shape implements this trait: `Rect` and `Circle` aren't
+/// closed under a general `Affine` (a rotated rect isn't a `Rect`, a
+/// non-uniformly scaled circle isn't a `Circle`), so they keep their
+/// existing, more specific `Mul` impls instead.
+pub trait Transformable: Sized {
+    /// Apply `t` to `self`, returning the transformed value.
+    fn transform(&self, t: &Affine) -> Self;
+
+    /// Apply `t` to `self` in place.
+    ///
+    /// The default implementation calls [`transform`](#tymethod.transform)
+    /// and overwrites `self` with the result; implementors for which
+    /// in-place mutation can avoid an allocation (such as `BezPath`) should
+    /// override this.
+    fn transform_mut(&mut self, t: &Affine) {
+        *self = self.transform(t);
+    }
+
+    /// Apply `t` to `self`, returning the transformed value.
+    ///
+    /// This is the `TranslateScale` overload of
+    /// [`transform`](#tymethod.transform); it exists so that callers with a
+    /// `TranslateScale` in hand don't need to convert to `Affine` first.
+    /// The default implementation does exactly that conversion.
+    fn transform_translate_scale(&self, t: &TranslateScale) -> Self {
+        self.transform(&Affine::from(*t))
+    }
+
+    /// Apply `t` to `self` in place, using a `TranslateScale`.
+    fn transform_translate_scale_mut(&mut self, t: &TranslateScale) {
+        *self = self.transform_translate_scale(t);
+    }
+}
+
+impl Transformable for Point {
+    #[inline]
+    fn transform(&self, t: &Affine) -> Point {
+        *t * *self
+    }
+}
+
+impl Transformable for Line {
+    #[inline]
+    fn transform(&self, t: &Affine) -> Line {
+        *t * *self
+    }
+}
+
+impl Transformable for QuadBez {
+    #[inline]
+    fn transform(&self, t: &Affine) -> QuadBez {
+        *t * *self
+    }
+}
+
+impl Transformable for CubicBez {
+    #[inline]
+    fn transform(&self, t: &Affine) -> CubicBez {
+        *t * *self
+    }
+}
+
+impl Transformable for Arc {
+    #[inline]
+    fn transform(&self, t: &Affine) -> Arc {
+        *t * *self
+    }
+}
+
+impl Transformable for Ellipse {
+    #[inline]
+    fn transform(&self, t: &Affine) -> Ellipse {
+        *t * *self
+    }
+}
+
+impl Transformable for RoundedRect {
+    #[inline]
+    fn transform(&self, t: &Affine) -> RoundedRect {
+        *t * *self
+    }
+}
+
+impl Transformable for PathEl {
+    fn transform(&self, t: &Affine) -> PathEl {
+        let mut el = *self;
+        el.transform_mut(t);
+        el
+    }
+
+    fn transform_mut(&mut self, t: &Affine) {
+        match self {
+            PathEl::MoveTo(p) | PathEl::LineTo(p) => *p = *t * *p,
+            PathEl::QuadTo(p0, p1) => {
+                *p0 = *t * *p0;
+                *p1 = *t * *p1;
+            }
+            PathEl::CurveTo(p0, p1, p2) => {
+                *p0 = *t * *p0;
+                *p1 = *t * *p1;
+                *p2 = *t * *p2;
+            }
+            PathEl::ClosePath => (),
+        }
+    }
+}
+
+impl Transformable for PathSeg {
+    fn transform(&self, t: &Affine) -> PathSeg {
+        match self {
+            PathSeg::Line(line) => PathSeg::Line(line.transform(t)),
+            PathSeg::Quad(quad) => PathSeg::Quad(quad.transform(t)),
+            PathSeg::Cubic(cubic) => PathSeg::Cubic(cubic.transform(t)),
+        }
+    }
+}
+
+impl Transformable for BezPath {
+    fn transform(&self, t: &Affine) -> BezPath {
+        let mut path = self.clone();
+        path.transform_mut(t);
+        path
+    }
+
+    fn transform_mut(&mut self, t: &Affine) {
+        for el in self.elements_mut() {
+            el.transform_mut(t);
+        }
+    }
+}
@@ -1,8 +1,16 @@
 //! A transformation that includes both scale and translation.
 
+use std::convert::TryFrom;
 use std::ops::{Add, AddAssign, Mul, MulAssign, Sub, SubAssign};
 
-use crate::{Affine, Circle, Point, Rect, Vec2};
+use crate::{Affine, Circle, Ellipse, Point, Rect, Vec2};
+
+/// The relative tolerance used by [`TranslateScale::try_from_affine`] when
+/// checking an `Affine` for rotation, shear or non-uniform scale. It is
+/// multiplied by the larger of `|a|`, `|d|` and `1.0` (the affine's
+/// diagonal coefficients) so that ordinary floating-point rounding doesn't
+/// cause large scale factors to spuriously fail the check.
+const TRY_FROM_AFFINE_EPSILON: f64 = 1e-9;
 
 /// A transformation including scaling and translation.
 ///
@@ -28,6 +36,8 @@ use crate::{Affine, Circle, Point, Rect, Vec2};
 /// `TranslateScale::translate(Vec2::new(1.0, 0.0)) * 2.0` has a
 /// translation of (1, 0). (Both have a scale of 2).
 #[derive(Clone, Copy, Debug)]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
 pub struct TranslateScale {
     translation: Vec2,
     scale: f64,
@@ -71,6 +81,31 @@ impl TranslateScale {
             scale: scale_recip,
         }
     }
+
+    /// Try to decompose an `Affine` into a `TranslateScale`.
+    ///
+    /// Returns `Some` only when `a` has no rotation or shear and a uniform
+    /// scale, i.e. its coefficients `[a, b, c, d, e, f]` satisfy `b ≈ 0`,
+    /// `c ≈ 0` and `a ≈ d` (within [`TRY_FROM_AFFINE_EPSILON`] times the
+    /// larger of `|a|`, `|d|` and `1.0`). Otherwise
+    /// `a` cannot be represented as a `TranslateScale` without losing
+    /// information, and this returns `None`.
+    ///
+    /// This is useful for downgrading a general `Affine` to the cheaper
+    /// `TranslateScale` representation once a caller has confirmed (by
+    /// getting `Some` back) that doing so is lossless, for example to
+    /// speed up a fast rendering path or to cache a cheaper transform.
+    pub fn try_from_affine(a: Affine) -> Option<TranslateScale> {
+        let c = a.as_coeffs();
+        let [a0, b, c0, d, e, f] = c;
+        let scale = a0.abs().max(d.abs()).max(1.0);
+        let epsilon = TRY_FROM_AFFINE_EPSILON * scale;
+        if b.abs() < epsilon && c0.abs() < epsilon && (a0 - d).abs() < epsilon {
+            Some(TranslateScale::new(Vec2::new(e, f), a0))
+        } else {
+            None
+        }
+    }
 }
 
 impl Default for TranslateScale {
@@ -87,6 +122,18 @@ impl From<TranslateScale> for Affine {
     }
 }
 
+/// Tries to decompose `a` into a `TranslateScale`; see
+/// [`TranslateScale::try_from_affine`] for the conditions under which this
+/// succeeds.
+impl TryFrom<Affine> for TranslateScale {
+    type Error = ();
+
+    #[inline]
+    fn try_from(a: Affine) -> Result<TranslateScale, ()> {
+        TranslateScale::try_from_affine(a).ok_or(())
+    }
+}
+
 impl Mul<Point> for TranslateScale {
     type Output = Point;
 
@@ -96,6 +143,21 @@ impl Mul<Point> for TranslateScale {
     }
 }
 
+/// Transforms a vector, as opposed to [`Mul<Point>`](#impl-Mul<Point>),
+/// which transforms a point.
+///
+/// Vectors represent a direction and magnitude (a tangent, a normal, a
+/// displacement) rather than a location, so translation does not apply to
+/// them; only the scale component does.
+impl Mul<Vec2> for TranslateScale {
+    type Output = Vec2;
+
+    #[inline]
+    fn mul(self, other: Vec2) -> Vec2 {
+        self.scale * other
+    }
+}
+
 impl Mul for TranslateScale {
     type Output = TranslateScale;
 
@@ -206,9 +268,177 @@ impl Mul<Rect> for TranslateScale {
     }
 }
 
+/// A transformation including non-uniform scaling and translation.
+///
+/// Unlike [`TranslateScale`](struct.TranslateScale.html), the x and y axes
+/// can be scaled independently, which makes this type cheaper than a full
+/// [`Affine`](struct.Affine.html) for things like anisotropic DPI handling
+/// or fitting a [`Rect`](struct.Rect.html) into another `Rect`, while still
+/// being cheaper than `Affine` for the common case where no rotation or
+/// shear is involved.
+///
+/// If the translation is `(x, y)` and the scale is `(sx, sy)`, then this
+/// transformation represents this augmented matrix:
+///
+/// ```text
+/// | sx 0  x |
+/// | 0  sy y |
+/// | 0  0  1 |
+/// ```
+///
+/// As with `TranslateScale`, multiplication is defined to be consistent
+/// with matrix multiplication, and is not commutative.
+#[derive(Clone, Copy, Debug)]
+#[repr(C)]
+#[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+pub struct ScaleTranslate {
+    translation: Vec2,
+    scale: Vec2,
+}
+
+impl ScaleTranslate {
+    /// Create a new transformation from translation and scale.
+    #[inline]
+    pub const fn new(translation: Vec2, scale: Vec2) -> ScaleTranslate {
+        ScaleTranslate { translation, scale }
+    }
+
+    /// Create a new transformation with scale only.
+    #[inline]
+    pub const fn scale(s: Vec2) -> ScaleTranslate {
+        ScaleTranslate::new(Vec2::ZERO, s)
+    }
+
+    /// Create a new transformation with translation only.
+    #[inline]
+    pub const fn translate(t: Vec2) -> ScaleTranslate {
+        ScaleTranslate::new(t, Vec2::new(1.0, 1.0))
+    }
+
+    /// Decompose transformation into translation and scale.
+    pub fn as_tuple(self) -> (Vec2, Vec2) {
+        (self.translation, self.scale)
+    }
+
+    /// Compute the inverse transform.
+    ///
+    /// Multiplying a transform with its inverse (either on the
+    /// left or right) results in the identity transform
+    /// (modulo floating point rounding errors).
+    ///
+    /// Panics when either scale component is zero.
+    pub fn inverse(self) -> ScaleTranslate {
+        assert!(
+            self.scale.x != 0.0 && self.scale.y != 0.0,
+            "ScaleTranslate::inverse: scale must be non-zero, got {:?}",
+            self.scale
+        );
+        let scale_recip = Vec2::new(self.scale.x.recip(), self.scale.y.recip());
+        ScaleTranslate {
+            translation: Vec2::new(
+                -self.translation.x * scale_recip.x,
+                -self.translation.y * scale_recip.y,
+            ),
+            scale: scale_recip,
+        }
+    }
+}
+
+impl Default for ScaleTranslate {
+    #[inline]
+    fn default() -> ScaleTranslate {
+        ScaleTranslate::scale(Vec2::new(1.0, 1.0))
+    }
+}
+
+impl From<ScaleTranslate> for Affine {
+    fn from(st: ScaleTranslate) -> Affine {
+        let ScaleTranslate { translation, scale } = st;
+        Affine::new([scale.x, 0.0, 0.0, scale.y, translation.x, translation.y])
+    }
+}
+
+impl Mul<Point> for ScaleTranslate {
+    type Output = Point;
+
+    #[inline]
+    fn mul(self, other: Point) -> Point {
+        Point::new(
+            self.scale.x * other.x + self.translation.x,
+            self.scale.y * other.y + self.translation.y,
+        )
+    }
+}
+
+/// Transforms a vector, as opposed to [`Mul<Point>`](#impl-Mul<Point>),
+/// which transforms a point; translation does not apply to vectors, so
+/// only the scale component is used.
+impl Mul<Vec2> for ScaleTranslate {
+    type Output = Vec2;
+
+    #[inline]
+    fn mul(self, other: Vec2) -> Vec2 {
+        Vec2::new(self.scale.x * other.x, self.scale.y * other.y)
+    }
+}
+
+impl Mul for ScaleTranslate {
+    type Output = ScaleTranslate;
+
+    #[inline]
+    fn mul(self, other: ScaleTranslate) -> ScaleTranslate {
+        ScaleTranslate {
+            translation: self.translation
+                + Vec2::new(
+                    self.scale.x * other.translation.x,
+                    self.scale.y * other.translation.y,
+                ),
+            scale: Vec2::new(self.scale.x * other.scale.x, self.scale.y * other.scale.y),
+        }
+    }
+}
+
+impl MulAssign for ScaleTranslate {
+    #[inline]
+    fn mul_assign(&mut self, other: ScaleTranslate) {
+        *self = self.mul(other);
+    }
+}
+
+impl Mul<Rect> for ScaleTranslate {
+    type Output = Rect;
+
+    #[inline]
+    fn mul(self, other: Rect) -> Rect {
+        let pt0 = self * Point::new(other.x0, other.y0);
+        let pt1 = self * Point::new(other.x1, other.y1);
+        (pt0, pt1).into()
+    }
+}
+
+impl Mul<Circle> for ScaleTranslate {
+    type Output = Ellipse;
+
+    /// Apply a `ScaleTranslate` to a `Circle`.
+    ///
+    /// A non-uniform scale turns a circle into an ellipse, so this always
+    /// returns an `Ellipse`, even when `self.scale.x == self.scale.y` (in
+    /// which case the result happens to be circular).
+    #[inline]
+    fn mul(self, other: Circle) -> Ellipse {
+        Ellipse::new(
+            self * other.center,
+            Vec2::new(self.scale.x * other.radius, self.scale.y * other.radius),
+            0.0,
+        )
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::{Affine, Point, TranslateScale, Vec2};
+    use std::convert::TryFrom;
+
+    use crate::{Affine, Point, ScaleTranslate, TranslateScale, Vec2};
 
     fn assert_near(p0: Point, p1: Point) {
         assert!((p1 - p0).hypot() < 1e-9, "{:?} != {:?}", p0, p1);
@@ -237,6 +467,15 @@ mod tests {
         assert_near(p + t, TranslateScale::translate(t) * p);
     }
 
+    #[test]
+    fn translate_scale_vector() {
+        let v = Vec2::new(3.0, 4.0);
+        let ts = TranslateScale::new(Vec2::new(5.0, 6.0), 2.0);
+
+        // Vectors are scaled but not translated.
+        assert_near((ts * v).to_point(), Point::new(6.0, 8.0));
+    }
+
     #[test]
     fn inverse() {
         let p = Point::new(3.0, 4.0);
@@ -245,4 +484,108 @@ mod tests {
         assert_near(p, (ts * ts.inverse()) * p);
         assert_near(p, (ts.inverse() * ts) * p);
     }
+
+    #[test]
+    fn scale_translate() {
+        let p = Point::new(3.0, 4.0);
+        let st = ScaleTranslate::new(Vec2::new(5.0, 6.0), Vec2::new(2.0, 3.0));
+
+        assert_near(st * p, Point::new(11.0, 18.0));
+    }
+
+    #[test]
+    fn scale_translate_conversions() {
+        let p = Point::new(3.0, 4.0);
+        let s = Vec2::new(2.0, 3.0);
+        let t = Vec2::new(5.0, 6.0);
+        let st = ScaleTranslate::new(t, s);
+
+        // Test that conversion to affine is consistent.
+        let a: Affine = st.into();
+        assert_near(st * p, a * p);
+
+        assert_near(
+            Point::new(s.x * p.x, s.y * p.y),
+            ScaleTranslate::scale(s) * p,
+        );
+        assert_near(p + t, ScaleTranslate::translate(t) * p);
+    }
+
+    #[test]
+    fn scale_translate_vector() {
+        let v = Vec2::new(3.0, 4.0);
+        let st = ScaleTranslate::new(Vec2::new(5.0, 6.0), Vec2::new(2.0, 3.0));
+
+        // Vectors are scaled but not translated.
+        assert_near((st * v).to_point(), Point::new(6.0, 12.0));
+    }
+
+    #[test]
+    fn scale_translate_inverse() {
+        let p = Point::new(3.0, 4.0);
+        let st = ScaleTranslate::new(Vec2::new(5.0, 6.0), Vec2::new(2.0, 3.0));
+
+        assert_near(p, (st * st.inverse()) * p);
+        assert_near(p, (st.inverse() * st) * p);
+    }
+
+    #[test]
+    #[should_panic]
+    fn scale_translate_inverse_panics_on_zero_scale() {
+        ScaleTranslate::new(Vec2::new(5.0, 6.0), Vec2::new(0.0, 3.0)).inverse();
+    }
+
+    #[test]
+    fn try_from_affine_roundtrip() {
+        let ts = TranslateScale::new(Vec2::new(5.0, 6.0), 2.0);
+        let a: Affine = ts.into();
+
+        let back = TranslateScale::try_from_affine(a).expect("should decompose");
+        assert_eq!(back.as_tuple(), ts.as_tuple());
+
+        assert!(TranslateScale::try_from(a).is_ok());
+    }
+
+    #[test]
+    fn try_from_affine_roundtrip_large_scale() {
+        // A large uniform scale must still decompose, and its scale factor
+        // must come back unchanged.
+        let ts = TranslateScale::new(Vec2::new(5.0, 6.0), 1e6);
+        let a: Affine = ts.into();
+
+        let back = TranslateScale::try_from_affine(a).expect("should decompose");
+        assert_near(Point::new(back.as_tuple().1, 0.0), Point::new(1e6, 0.0));
+    }
+
+    #[test]
+    fn try_from_affine_rejects_rotation_and_shear() {
+        // A 90 degree rotation has no equivalent TranslateScale.
+        let rotation = Affine::new([0.0, 1.0, -1.0, 0.0, 0.0, 0.0]);
+        assert!(TranslateScale::try_from_affine(rotation).is_none());
+
+        // Non-uniform scale has no equivalent TranslateScale either.
+        let non_uniform = Affine::new([2.0, 0.0, 0.0, 3.0, 0.0, 0.0]);
+        assert!(TranslateScale::try_from_affine(non_uniform).is_none());
+        assert!(TranslateScale::try_from(non_uniform).is_err());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn translate_scale_bytemuck_roundtrip() {
+        let ts = TranslateScale::new(Vec2::new(5.0, 6.0), 2.0);
+        let bytes = bytemuck::bytes_of(&ts);
+        let back: TranslateScale = *bytemuck::from_bytes(bytes);
+        assert_eq!(back.as_tuple(), ts.as_tuple());
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn scale_translate_bytemuck_roundtrip() {
+        let st = ScaleTranslate::new(Vec2::new(5.0, 6.0), Vec2::new(2.0, 3.0));
+        let slice = [st, st];
+        let bytes: &[u8] = bytemuck::cast_slice(&slice);
+        let back: &[ScaleTranslate] = bytemuck::cast_slice(bytes);
+        assert_eq!(back[0].as_tuple(), st.as_tuple());
+        assert_eq!(back[1].as_tuple(), st.as_tuple());
+    }
 }